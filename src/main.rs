@@ -1,6 +1,8 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use db_dump::categories::CategoryId;
+use db_dump::crate_owners::OwnerId;
 use db_dump::crates::{CrateId, Row};
+use db_dump::dependencies::DependencyKind;
 use db_dump::keywords::KeywordId;
 use db_dump::versions::VersionId;
 use reqwest::Client;
@@ -12,21 +14,120 @@ use std::fs::{self, File};
 use std::io::Write;
 use std::process::exit;
 
+/// Identifies the `dump` container format so a reader can reject a file
+/// written by an incompatible version instead of misparsing it.
+const MAGIC: &[u8; 8] = b"CRATEDMP";
+const FORMAT_VERSION: u16 = 2;
+
+/// FNV-1a hash of a crate name, used as the fixed-width footer index key so a
+/// reader can binary-search the index without ever parsing a variable-length
+/// name field.
+fn fnv1a_hash(name: &str) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+    name.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Byte width of a single footer index entry: name_hash(u64) + offset(u64) +
+/// length(u32).
+const FOOTER_ENTRY_LEN: usize = 8 + 8 + 4;
+
+/// Sorts `index` by name hash and serializes it into a fixed-width footer:
+/// a u32 entry count followed by `FOOTER_ENTRY_LEN`-byte entries, so a reader
+/// can binary search the index by seeking straight to entry `mid` without
+/// parsing any entry before it.
+fn encode_footer(index: &mut [(u64, u64, u32)]) -> Vec<u8> {
+    index.sort_unstable_by_key(|&(name_hash, _, _)| name_hash);
+    let mut footer = Vec::with_capacity(4 + index.len() * FOOTER_ENTRY_LEN);
+    footer.extend(&(index.len() as u32).to_le_bytes());
+    for (name_hash, record_offset, length) in index {
+        footer.extend(&name_hash.to_le_bytes());
+        footer.extend(&record_offset.to_le_bytes());
+        footer.extend(&length.to_le_bytes());
+    }
+    footer
+}
+
+// Weights for the composite score in `compute_score`. Each signal is
+// log-scaled first so that, say, a crate with a million downloads doesn't
+// swamp everything else by raw magnitude; the weight then controls how much
+// that signal matters relative to the others.
+const SCORE_WEIGHT_REQUIRED_RDEPS: f32 = 1.5;
+const SCORE_WEIGHT_RECENT_DOWNLOADS: f32 = 1.0;
+const SCORE_WEIGHT_TOTAL_DOWNLOADS: f32 = 0.5;
+const SCORE_WEIGHT_NUM_VERSIONS: f32 = 0.25;
+const SCORE_WEIGHT_AGE: f32 = 0.75;
+
+/// Blends reverse-dependency, download, version-count and freshness signals
+/// into a single sortable number. Every input is log-scaled so that no one
+/// signal's raw magnitude drowns out the others, and age is subtracted
+/// (rather than cut off) so a popular-but-quiet crate decays gently instead
+/// of falling off a cliff.
+fn compute_score(
+    required_rdeps: u32,
+    recent_downloads: u32,
+    total_downloads: u64,
+    num_versions: u32,
+    age_days: f32,
+) -> f32 {
+    SCORE_WEIGHT_REQUIRED_RDEPS * (1.0 + required_rdeps as f32).ln()
+        + SCORE_WEIGHT_RECENT_DOWNLOADS * (1.0 + recent_downloads as f32).ln()
+        + SCORE_WEIGHT_TOTAL_DOWNLOADS * (1.0 + total_downloads as f32).ln()
+        + SCORE_WEIGHT_NUM_VERSIONS * (1.0 + num_versions as f32).ln()
+        - SCORE_WEIGHT_AGE * (1.0 + age_days.max(0.0)).ln()
+}
+
 #[tokio::main]
 async fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        println!("Usage: dump \"date here\"");
+    let allow_prerelease = args.iter().any(|arg| arg == "--allow-prerelease");
+    let positional: Vec<&String> = args
+        .iter()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+    if positional.is_empty() || positional.len() > 2 {
+        println!("Usage: dump \"date here\" [rdeps|downloads|score] [--allow-prerelease]");
         exit(1);
     }
-    let update = download_if_updated(args.get(1).unwrap()).await;
-    let data = process().unwrap();
+    let sort_mode = positional
+        .get(1)
+        .map(|arg| SortMode::parse(arg))
+        .unwrap_or(SortMode::ReverseDeps);
+    let update = download_if_updated(positional[0]).await;
+    let reference_date = DateTime::parse_from_rfc2822(update.trim())
+        .unwrap()
+        .with_timezone(&Utc);
+    let data = process(reference_date, sort_mode, allow_prerelease).unwrap();
     let mut file = File::create("dump").unwrap();
+    file.write_all(MAGIC).unwrap();
+    file.write_all(&FORMAT_VERSION.to_le_bytes()).unwrap();
+
+    // Byte offset of each crate's record (past its length prefix), so the
+    // footer index below can point straight at the payload.
+    let mut offset = (MAGIC.len() + 2) as u64;
+    let mut index = Vec::with_capacity(data.len());
     for crat in data {
+        let name_hash = fnv1a_hash(&crat.name);
         let by = crat.to_vec();
         file.write_all(&(by.len() as u32).to_le_bytes()).unwrap();
+        offset += 4;
         file.write_all(&by).unwrap();
+        index.push((name_hash, offset, by.len() as u32));
+        offset += by.len() as u64;
     }
+
+    // Footer: a hash-sorted, fixed-width index so a consumer can mmap the
+    // file and binary search straight to a crate's record without parsing
+    // any preceding entry. Each entry is name_hash(u64) + offset(u64) +
+    // length(u32) = 20 bytes. Entries are keyed by name hash only; the
+    // record's own name field is what tells apart names that share a hash.
+    let footer_offset = offset;
+    file.write_all(&encode_footer(&mut index)).unwrap();
+    file.write_all(&footer_offset.to_le_bytes()).unwrap();
+
     File::create("last_updated")
         .unwrap()
         .write_all(update.as_bytes())
@@ -75,7 +176,71 @@ async fn download_if_updated(last: &str) -> String {
     }
 }
 
-fn process() -> db_dump::Result<Vec<Crate>> {
+/// How the final crate list should be ordered.
+#[derive(Debug, Clone, Copy)]
+enum SortMode {
+    /// Most reverse dependencies first (the original behavior).
+    ReverseDeps,
+    /// Most downloads in the trailing 90 days first.
+    Downloads,
+    /// Highest composite score first, see `compute_score`.
+    Score,
+}
+
+impl SortMode {
+    fn parse(arg: &str) -> Self {
+        match arg {
+            "rdeps" => SortMode::ReverseDeps,
+            "downloads" => SortMode::Downloads,
+            "score" => SortMode::Score,
+            other => {
+                println!(
+                    "Unknown sort mode {other:?}, expected \"rdeps\", \"downloads\" or \"score\""
+                );
+                exit(1);
+            }
+        }
+    }
+}
+
+/// Picks the version that should win the `latest_version` field out of the
+/// greatest non-yanked stable and prerelease versions. A stable release
+/// always outranks a prerelease unless `allow_prerelease` is set, in which
+/// case the numerically greater of the two wins.
+fn resolve_latest_version<'a>(
+    stable: Option<&'a semver::Version>,
+    prerelease: Option<&'a semver::Version>,
+    allow_prerelease: bool,
+) -> Option<&'a semver::Version> {
+    match (stable, prerelease) {
+        (Some(stable), Some(prerelease)) => {
+            if allow_prerelease && prerelease > stable {
+                Some(prerelease)
+            } else {
+                Some(stable)
+            }
+        }
+        (Some(stable), None) => Some(stable),
+        (None, Some(prerelease)) => Some(prerelease),
+        (None, None) => None,
+    }
+}
+
+/// Stable discriminant for `DependencyKind`, used as a key in ordered
+/// collections since the type itself doesn't implement `Ord`.
+fn dependency_kind_tag(kind: DependencyKind) -> u8 {
+    match kind {
+        DependencyKind::Normal => 0,
+        DependencyKind::Build => 1,
+        DependencyKind::Dev => 2,
+    }
+}
+
+fn process(
+    reference_date: DateTime<Utc>,
+    sort_mode: SortMode,
+    allow_prerelease: bool,
+) -> db_dump::Result<Vec<Crate>> {
     // Map of crate id to the most recently published version of that crate.
     let mut most_recent = Map::new();
 
@@ -88,7 +253,13 @@ fn process() -> db_dump::Result<Vec<Crate>> {
     let mut version_count = Map::<CrateId, u32>::new();
     let mut libs = Set::<CrateId>::new();
     let mut stable_versions = Map::<CrateId, semver::Version>::new();
-    let mut versions = Map::<CrateId, semver::Version>::new();
+    let mut prerelease_versions = Map::<CrateId, semver::Version>::new();
+    let mut version_crate_id = Map::<VersionId, CrateId>::new();
+    let mut version_downloads = Vec::new();
+    let mut crate_owners: Map<CrateId, Vec<(OwnerKind, u32)>> = Map::new();
+    let mut owner_refs = Set::<(OwnerKind, u32)>::new();
+    let mut user_logins = Map::<u32, (String, Option<String>)>::new();
+    let mut team_logins = Map::<u32, (String, Option<String>)>::new();
     db_dump::Loader::new()
         .crates(|row| {
             crates.insert(row);
@@ -96,31 +267,24 @@ fn process() -> db_dump::Result<Vec<Crate>> {
         .dependencies(|row| dependencies.push(row))
         .versions(|row| {
             let v = &row.num;
-            match v.pre.is_empty() {
-                true => {
-                    stable_versions
-                        .entry(row.crate_id)
-                        .and_modify(|old_version| {
-                            if *old_version < *v {
-                                *old_version = v.clone();
-                            }
-                        })
-                        .or_insert(v.clone());
-                }
-                false => {
-                    versions
-                        .entry(row.crate_id)
-                        .and_modify(|old_version| {
-                            if *old_version < *v {
-                                *old_version = v.clone();
-                            }
-                        })
-                        .or_insert(v.clone());
-                }
-            };
+            if !row.yanked {
+                let channel = match v.pre.is_empty() {
+                    true => &mut stable_versions,
+                    false => &mut prerelease_versions,
+                };
+                channel
+                    .entry(row.crate_id)
+                    .and_modify(|old_version| {
+                        if *old_version < *v {
+                            *old_version = v.clone();
+                        }
+                    })
+                    .or_insert(v.clone());
+            }
             if row.has_lib {
                 libs.insert(row.crate_id);
             }
+            version_crate_id.insert(row.id, row.crate_id);
             match most_recent.entry(row.crate_id) {
                 Entry::Vacant(entry) => {
                     entry.insert(row);
@@ -135,6 +299,23 @@ fn process() -> db_dump::Result<Vec<Crate>> {
         .default_versions(|row| {
             version_count.insert(row.crate_id, row.num_versions.unwrap_or_default());
         })
+        .version_downloads(|row| {
+            version_downloads.push((row.version_id, row.downloads, row.date));
+        })
+        .crate_owners(|row| {
+            let owner = match row.owner_id {
+                OwnerId::User(id) => (OwnerKind::User, id.0),
+                OwnerId::Team(id) => (OwnerKind::Team, id.0),
+            };
+            crate_owners.entry(row.crate_id).or_default().push(owner);
+            owner_refs.insert(owner);
+        })
+        .users(|row| {
+            user_logins.insert(row.id.0, (row.gh_login.clone(), row.name.clone()));
+        })
+        .teams(|row| {
+            team_logins.insert(row.id.0, (row.login.clone(), Some(row.name.clone())));
+        })
         .crates_keywords(|row| {
             crate_keywords
                 .entry(row.crate_id)
@@ -154,36 +335,120 @@ fn process() -> db_dump::Result<Vec<Crate>> {
             all_categories.insert(row.id, row.category.clone());
         })
         .load("./db-dump.tar.gz")?;
+
+    // Per-crate download totals: an all-time sum and a trailing-90-day sum
+    // relative to the dump's `last_updated` timestamp.
+    let trailing_window_start = reference_date.date_naive() - Duration::days(90);
+    let mut recent_downloads = Map::<CrateId, u32>::new();
+    let mut total_downloads = Map::<CrateId, u64>::new();
+    for (version_id, downloads, date) in version_downloads {
+        if let Some(crate_id) = version_crate_id.get(&version_id) {
+            *total_downloads.entry(*crate_id).or_default() += downloads;
+            if date.naive_utc() >= trailing_window_start {
+                *recent_downloads.entry(*crate_id).or_default() += downloads as u32;
+            }
+        }
+    }
+
+    // Deduplicated table of every owner (user or team) referenced by a
+    // crate_owners row, resolved against the users/teams tables. Keyed by
+    // (kind, id) since user ids and team ids are independent sequences.
+    let mut owners_table = Map::<(OwnerKind, u32), (String, Option<String>)>::new();
+    for &(kind, id) in &owner_refs {
+        let (login, name) = match kind {
+            OwnerKind::User => user_logins.get(&id).cloned(),
+            OwnerKind::Team => team_logins.get(&id).cloned(),
+        }
+        .unwrap_or_default();
+        owners_table.insert((kind, id), (login, name));
+    }
+
     let crates = crates
         .into_iter()
         .filter(|c| libs.contains(&c.id))
         .collect::<Set<Row>>();
 
+    // Publish date of each crate's most recently published version, used to
+    // compute the freshness term of the composite score below.
+    let most_recent_created_at: Map<CrateId, chrono::DateTime<Utc>> = most_recent
+        .values()
+        .map(|version| (version.crate_id, version.created_at))
+        .collect();
+
     // Set of version ids which are the most recently published of their crate.
     let most_recent = Set::from_iter(most_recent.values().map(|version| version.id));
 
-    // Set of (version id, dependency crate id) pairs to avoid double-counting
-    // cases where a crate has both a normal dependency and dev-dependency or
-    // build-dependency on the same dependency crate.
-    let mut unique_dependency_edges = Set::<(VersionId, CrateId)>::new();
+    // Set of (version id, dependency crate id, kind, optional) tuples to avoid
+    // double-counting duplicate dependency rows (e.g. the same dependency
+    // listed once per target platform) within a single tally. `DependencyKind`
+    // doesn't implement `Ord`, so it's represented here by its discriminant.
+    let mut unique_dependency_edges = Set::<(VersionId, CrateId, u8, bool)>::new();
 
-    // Map of crate id to how many other crates' most recent version depends on that crate.
-    let mut count = Map::<CrateId, usize>::new();
+    // Map of crate id to how many other crates' most recent version depends on
+    // that crate, broken down by dependency kind and optionality.
+    let mut dep_counts = Map::<CrateId, DepCounts>::new();
     for dep in dependencies {
         if most_recent.contains(&dep.version_id)
-            && unique_dependency_edges.insert((dep.version_id, dep.crate_id))
+            && unique_dependency_edges.insert((
+                dep.version_id,
+                dep.crate_id,
+                dependency_kind_tag(dep.kind),
+                dep.optional,
+            ))
         {
-            *count.entry(dep.crate_id).or_default() += 1;
+            let counts = dep_counts.entry(dep.crate_id).or_default();
+            match dep.kind {
+                DependencyKind::Normal if dep.optional => counts.optional_normal += 1,
+                DependencyKind::Normal => counts.required_normal += 1,
+                DependencyKind::Build => counts.build += 1,
+                DependencyKind::Dev => counts.dev += 1,
+            }
         }
     }
 
     for crate_id in &crates {
-        count.entry(crate_id.id).or_insert(0);
+        dep_counts.entry(crate_id.id).or_default();
+    }
+
+    // Composite score per crate, see `compute_score`.
+    let mut scores = Map::<CrateId, f32>::new();
+    for crate_id in &crates {
+        let id = crate_id.id;
+        let age_days = most_recent_created_at
+            .get(&id)
+            .map(|created_at| (reference_date - *created_at).num_days() as f32)
+            .unwrap_or_default();
+        scores.insert(
+            id,
+            compute_score(
+                dep_counts.get(&id).copied().unwrap_or_default().required_normal,
+                recent_downloads.get(&id).copied().unwrap_or_default(),
+                total_downloads.get(&id).copied().unwrap_or_default(),
+                version_count.get(&id).copied().unwrap_or_default(),
+                age_days,
+            ),
+        );
     }
 
     // Optional: Sort all crates by count descending
-    let mut all_crates: Vec<_> = count.into_iter().collect();
-    all_crates.sort_unstable_by_key(|&(_, count)| Reverse(count));
+    let mut all_crates: Vec<_> = dep_counts.into_iter().collect();
+    match sort_mode {
+        SortMode::ReverseDeps => {
+            all_crates.sort_unstable_by_key(|&(_, counts)| Reverse(counts.total()));
+        }
+        SortMode::Downloads => {
+            all_crates.sort_unstable_by_key(|&(id, _)| {
+                Reverse(recent_downloads.get(&id).copied().unwrap_or_default())
+            });
+        }
+        SortMode::Score => {
+            all_crates.sort_unstable_by(|&(a, _), &(b, _)| {
+                let score_a = scores.get(&a).copied().unwrap_or_default();
+                let score_b = scores.get(&b).copied().unwrap_or_default();
+                score_b.total_cmp(&score_a)
+            });
+        }
+    }
     let mut keywords = File::create("keywords").unwrap();
     keywords
         .write_all(
@@ -218,19 +483,49 @@ fn process() -> db_dump::Result<Vec<Crate>> {
                 .as_slice(),
         )
         .unwrap();
+    let mut owners = File::create("owners").unwrap();
+    owners
+        .write_all(
+            owners_table
+                .into_iter()
+                .map(|((kind, id), (login, name))| {
+                    let mut bytes: Vec<u8> = vec![];
+                    bytes.extend(&id.to_le_bytes());
+                    bytes.push(match kind {
+                        OwnerKind::User => 0u8,
+                        OwnerKind::Team => 1u8,
+                    });
+                    bytes.extend(&(login.len() as u32).to_le_bytes());
+                    bytes.extend(login.as_bytes());
+                    let name = name.unwrap_or_default();
+                    bytes.extend(&(name.len() as u32).to_le_bytes());
+                    bytes.extend(name.as_bytes());
+                    bytes
+                })
+                .flatten()
+                .collect::<Vec<u8>>()
+                .as_slice(),
+        )
+        .unwrap();
     let mut out = vec![];
-    for (id, count) in all_crates {
+    for (id, counts) in all_crates {
         let crat = &crates.get(&id);
         if let Some(crat) = crat {
             out.push(Crate {
-                order: count as u32,
+                order: counts.total(),
+                dep_counts: counts,
                 name: crat.name.clone(),
                 repository: crat.repository.clone(),
                 homepage: crat.homepage.clone(),
                 documentation: crat.documentation.clone(),
                 description: crat.description.clone(),
                 latest_stable_version: stable_versions.get(&crat.id).map(|v| v.to_string()),
-                latest_version: versions.get(&crat.id).map(|v| v.to_string()),
+                latest_version: resolve_latest_version(
+                    stable_versions.get(&crat.id),
+                    prerelease_versions.get(&crat.id),
+                    allow_prerelease,
+                )
+                .map(|v| v.to_string()),
                 categories: crate_categories
                     .get(&crat.id)
                     .map(|v| v.into_iter().map(|v| v.0).collect())
@@ -240,14 +535,44 @@ fn process() -> db_dump::Result<Vec<Crate>> {
                     .map(|v| v.into_iter().map(|v| v.0).collect())
                     .unwrap_or_default(),
                 num_versions: version_count.get(&crat.id).map(|v| *v).unwrap_or_default(),
+                recent_downloads: recent_downloads.get(&crat.id).copied().unwrap_or_default(),
+                total_downloads: total_downloads.get(&crat.id).copied().unwrap_or_default(),
+                owners: crate_owners.get(&crat.id).cloned().unwrap_or_default(),
+                score: scores.get(&crat.id).copied().unwrap_or_default(),
             });
         }
     }
     Ok(out)
 }
 
+/// Reverse-dependency tallies for a crate, split by dependency kind and
+/// optionality so consumers can distinguish "1,200 crates depend on this,
+/// 340 only optionally" from a single undifferentiated count.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DepCounts {
+    required_normal: u32,
+    optional_normal: u32,
+    build: u32,
+    dev: u32,
+}
+
+impl DepCounts {
+    fn total(&self) -> u32 {
+        self.required_normal + self.optional_normal + self.build + self.dev
+    }
+}
+
+/// Which kind of crates.io account an owner id refers to. Users and teams
+/// have independent id sequences, so an owner must always be identified by
+/// `(OwnerKind, id)`, never by id alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OwnerKind {
+    User,
+    Team,
+}
+
 //keyword file, categories file
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Crate {
     name: String,
     repository: Option<String>,
@@ -258,33 +583,124 @@ pub struct Crate {
     latest_version: Option<String>,
     categories: Vec<u32>,
     keywords: Vec<u32>,
+    owners: Vec<(OwnerKind, u32)>,
     num_versions: u32,
     order: u32,
+    dep_counts: DepCounts,
+    recent_downloads: u32,
+    total_downloads: u64,
+    score: f32,
 }
 
-macro_rules! read_u32 {
-    ($data:expr, $cursor:expr) => {{
-        let value = u32::from_le_bytes($data[$cursor..$cursor + 4].try_into().unwrap());
-        $cursor += 4;
-        value
-    }};
+/// A bounds-checked cursor over a crate record's bytes. Every read returns
+/// `Err(ParseError::UnexpectedEof)` instead of panicking if it would run past
+/// the end of the record. Field values are not otherwise validated beyond
+/// UTF-8 for strings and the owner kind tag.
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
 }
 
-macro_rules! read_string {
-    ($data:expr, $cursor:expr) => {{
-        let len = read_u32!($data, $cursor);
-        let s = String::from_utf8($data[$cursor..$cursor + len as usize].to_vec()).unwrap();
-        $cursor += len as usize;
-        s
-    }};
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Cursor { data, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ParseError> {
+        let end = self.pos.checked_add(len).ok_or(ParseError::UnexpectedEof)?;
+        let slice = self.data.get(self.pos..end).ok_or(ParseError::UnexpectedEof)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> Result<u32, ParseError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, ParseError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_f32(&mut self) -> Result<f32, ParseError> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_u32_vec(&mut self) -> Result<Vec<u32>, ParseError> {
+        let len = self.read_u32()? as usize;
+        let mut values = Vec::with_capacity(len);
+        for _ in 0..len {
+            values.push(self.read_u32()?);
+        }
+        Ok(values)
+    }
+
+    fn read_string(&mut self) -> Result<String, ParseError> {
+        let len = self.read_u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(ParseError::InvalidUtf8)
+    }
+
+    fn read_optional_string(&mut self) -> Result<Option<String>, ParseError> {
+        let s = self.read_string()?;
+        Ok(if s.is_empty() { None } else { Some(s) })
+    }
+
+    fn read_owners(&mut self) -> Result<Vec<(OwnerKind, u32)>, ParseError> {
+        let len = self.read_u32()? as usize;
+        let mut values = Vec::with_capacity(len);
+        for _ in 0..len {
+            let id = self.read_u32()?;
+            let kind = match self.read_u8()? {
+                0 => OwnerKind::User,
+                1 => OwnerKind::Team,
+                tag => return Err(ParseError::InvalidOwnerKind(tag)),
+            };
+            values.push((kind, id));
+        }
+        Ok(values)
+    }
+}
+
+/// Why a crate record failed to parse out of a `dump` file.
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    /// The record ended before all of its fields were read, e.g. it was
+    /// truncated or sliced with the wrong length.
+    UnexpectedEof,
+    /// A length-prefixed string wasn't valid UTF-8.
+    InvalidUtf8(std::string::FromUtf8Error),
+    /// An owner entry's kind tag byte wasn't 0 (user) or 1 (team).
+    InvalidOwnerKind(u8),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedEof => write!(f, "unexpected end of crate record"),
+            ParseError::InvalidUtf8(err) => write!(f, "invalid utf-8 in crate record: {err}"),
+            ParseError::InvalidOwnerKind(tag) => write!(f, "invalid owner kind tag: {tag}"),
+        }
+    }
 }
 
+impl std::error::Error for ParseError {}
+
 impl Crate {
     pub fn to_vec(self) -> Vec<u8> {
         let mut byte_array = Vec::new();
 
         byte_array.extend(&self.order.to_le_bytes());
+        byte_array.extend(&self.dep_counts.required_normal.to_le_bytes());
+        byte_array.extend(&self.dep_counts.optional_normal.to_le_bytes());
+        byte_array.extend(&self.dep_counts.build.to_le_bytes());
+        byte_array.extend(&self.dep_counts.dev.to_le_bytes());
         byte_array.extend(&self.num_versions.to_le_bytes());
+        byte_array.extend(&self.recent_downloads.to_le_bytes());
+        byte_array.extend(&self.total_downloads.to_le_bytes());
+        byte_array.extend(&self.score.to_le_bytes());
         byte_array.extend(&(self.keywords.len() as u32).to_le_bytes());
         for keyword in &self.keywords {
             byte_array.extend(&keyword.to_le_bytes());
@@ -293,6 +709,14 @@ impl Crate {
         for keyword in &self.categories {
             byte_array.extend(&keyword.to_le_bytes());
         }
+        byte_array.extend(&(self.owners.len() as u32).to_le_bytes());
+        for (kind, id) in &self.owners {
+            byte_array.extend(&id.to_le_bytes());
+            byte_array.push(match kind {
+                OwnerKind::User => 0u8,
+                OwnerKind::Team => 1u8,
+            });
+        }
         let mut add_str = |s: &str| {
             byte_array.extend(&(s.len() as u32).to_le_bytes());
             byte_array.extend(s.as_bytes());
@@ -307,78 +731,47 @@ impl Crate {
         byte_array
     }
 
-    pub fn from_vec(data: Vec<u8>) -> Self {
-        let mut cursor = 0;
-
-        let order = read_u32!(data, cursor);
-        let num_versions = read_u32!(data, cursor);
+    /// Parses a single crate record, as sliced out of a `dump` file by the
+    /// footer's `(name_hash, offset, length)` index. Every read is
+    /// bounds-checked against `data`, so a truncated or mis-sliced record
+    /// yields a `ParseError` instead of panicking.
+    pub fn from_slice(data: &[u8]) -> Result<Self, ParseError> {
+        let mut cursor = Cursor::new(data);
 
-        let keywords_len = read_u32!(data, cursor) as usize;
-        let mut keywords = Vec::with_capacity(keywords_len);
-        for _ in 0..keywords_len {
-            keywords.push(read_u32!(data, cursor));
-        }
+        let order = cursor.read_u32()?;
+        let dep_counts = DepCounts {
+            required_normal: cursor.read_u32()?,
+            optional_normal: cursor.read_u32()?,
+            build: cursor.read_u32()?,
+            dev: cursor.read_u32()?,
+        };
+        let num_versions = cursor.read_u32()?;
+        let recent_downloads = cursor.read_u32()?;
+        let total_downloads = cursor.read_u64()?;
+        let score = cursor.read_f32()?;
 
-        let categories_len = read_u32!(data, cursor) as usize;
-        let mut categories = Vec::with_capacity(categories_len);
-        for _ in 0..categories_len {
-            categories.push(read_u32!(data, cursor));
-        }
+        let keywords = cursor.read_u32_vec()?;
+        let categories = cursor.read_u32_vec()?;
+        let owners = cursor.read_owners()?;
 
-        let name = read_string!(data, cursor);
-        let description = read_string!(data, cursor);
-        let repository = if !data[cursor..].is_empty() {
-            let str = read_string!(data, cursor);
-            match str.len() == 0 {
-                true => None,
-                false => Some(str),
-            }
-        } else {
-            unreachable!()
-        };
-        let homepage = if !data[cursor..].is_empty() {
-            let str = read_string!(data, cursor);
-            match str.len() == 0 {
-                true => None,
-                false => Some(str),
-            }
-        } else {
-            unreachable!()
-        };
-        let documentation = if !data[cursor..].is_empty() {
-            let str = read_string!(data, cursor);
-            match str.len() == 0 {
-                true => None,
-                false => Some(str),
-            }
-        } else {
-            unreachable!();
-        };
-        let latest_stable_version = if !data[cursor..].is_empty() {
-            let str = read_string!(data, cursor);
-            match str.len() == 0 {
-                true => None,
-                false => Some(str),
-            }
-        } else {
-            unreachable!()
-        };
-        let latest_version = if !data[cursor..].is_empty() {
-            #[allow(unused_assignments)]
-            let str = read_string!(data, cursor);
-            match str.len() == 0 {
-                true => None,
-                false => Some(str),
-            }
-        } else {
-            unreachable!()
-        };
+        let name = cursor.read_string()?;
+        let description = cursor.read_string()?;
+        let repository = cursor.read_optional_string()?;
+        let homepage = cursor.read_optional_string()?;
+        let documentation = cursor.read_optional_string()?;
+        let latest_stable_version = cursor.read_optional_string()?;
+        let latest_version = cursor.read_optional_string()?;
 
-        Self {
+        Ok(Self {
             order,
+            dep_counts,
             num_versions,
+            recent_downloads,
+            total_downloads,
+            score,
             keywords,
             categories,
+            owners,
             name,
             description,
             repository,
@@ -386,6 +779,114 @@ impl Crate {
             documentation,
             latest_stable_version,
             latest_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_crate() -> Crate {
+        Crate {
+            name: "serde".to_string(),
+            repository: Some("https://github.com/serde-rs/serde".to_string()),
+            homepage: None,
+            documentation: Some("https://docs.rs/serde".to_string()),
+            description: "A generic serialization/deserialization framework".to_string(),
+            latest_stable_version: Some("1.0.200".to_string()),
+            latest_version: Some("1.0.200".to_string()),
+            categories: vec![1, 2],
+            keywords: vec![3, 4, 5],
+            owners: vec![(OwnerKind::User, 42), (OwnerKind::Team, 7)],
+            num_versions: 120,
+            order: 0,
+            dep_counts: DepCounts {
+                required_normal: 1000,
+                optional_normal: 50,
+                build: 0,
+                dev: 10,
+            },
+            recent_downloads: 123_456,
+            total_downloads: 9_876_543_210,
+            score: 42.5,
         }
     }
+
+    #[test]
+    fn to_vec_from_slice_round_trips() {
+        let crat = sample_crate();
+        let bytes = sample_crate().to_vec();
+        let parsed = Crate::from_slice(&bytes).unwrap();
+        assert_eq!(crat, parsed);
+    }
+
+    #[test]
+    fn from_slice_rejects_truncated_record() {
+        let bytes = sample_crate().to_vec();
+        for len in 0..bytes.len() {
+            assert_eq!(
+                Crate::from_slice(&bytes[..len]),
+                Err(ParseError::UnexpectedEof),
+                "expected truncation at {len} bytes to fail, not panic",
+            );
+        }
+    }
+
+    #[test]
+    fn from_slice_rejects_invalid_owner_kind() {
+        let bytes = sample_crate().to_vec();
+
+        // Walk the same fields `from_slice` does, up to the first owner's
+        // kind tag byte, then corrupt it to a value that isn't 0 (user) or 1
+        // (team).
+        let mut cursor = Cursor::new(&bytes);
+        cursor.read_u32().unwrap(); // order
+        for _ in 0..4 {
+            cursor.read_u32().unwrap(); // dep_counts fields
+        }
+        cursor.read_u32().unwrap(); // num_versions
+        cursor.read_u32().unwrap(); // recent_downloads
+        cursor.read_u64().unwrap(); // total_downloads
+        cursor.read_f32().unwrap(); // score
+        cursor.read_u32_vec().unwrap(); // keywords
+        cursor.read_u32_vec().unwrap(); // categories
+        let owner_count = cursor.read_u32().unwrap();
+        assert!(owner_count > 0, "sample_crate must have at least one owner");
+        cursor.read_u32().unwrap(); // first owner's id
+        let tag_pos = cursor.pos;
+
+        let mut corrupted = bytes.clone();
+        corrupted[tag_pos] = 2;
+
+        assert_eq!(
+            Crate::from_slice(&corrupted),
+            Err(ParseError::InvalidOwnerKind(2))
+        );
+    }
+
+    #[test]
+    fn encode_footer_is_sorted_and_fixed_width() {
+        let mut index = vec![(500u64, 10u64, 20u32), (100u64, 30u64, 40u32), (300u64, 50u64, 60u32)];
+        let entry_count = index.len();
+        let footer = encode_footer(&mut index);
+
+        assert_eq!(footer.len(), 4 + entry_count * FOOTER_ENTRY_LEN);
+        assert_eq!(u32::from_le_bytes(footer[0..4].try_into().unwrap()) as usize, entry_count);
+
+        let mut hashes = Vec::with_capacity(entry_count);
+        for i in 0..entry_count {
+            let entry = &footer[4 + i * FOOTER_ENTRY_LEN..4 + (i + 1) * FOOTER_ENTRY_LEN];
+            hashes.push(u64::from_le_bytes(entry[0..8].try_into().unwrap()));
+        }
+        let mut sorted = hashes.clone();
+        sorted.sort_unstable();
+        assert_eq!(hashes, sorted, "footer entries must be sorted by name hash for binary search");
+    }
+
+    #[test]
+    fn fnv1a_hash_is_deterministic_and_distinguishes_names() {
+        assert_eq!(fnv1a_hash("serde"), fnv1a_hash("serde"));
+        assert_ne!(fnv1a_hash("serde"), fnv1a_hash("serde_json"));
+    }
 }